@@ -1,11 +1,101 @@
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use crate::errors::Result;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+
+use crate::errors::{Error, Result};
+use crate::transports::retry::{RetryAction, RetryPolicy};
 use crate::transports::{BatchTransport, Transport};
 use crate::types::{Call, MethodCall, Params, Request, RequestId, Response, Version};
 
+/// Fixed JWT header used for the Engine-API-style HS256 tokens minted by
+/// [`HttpTransport::new_with_jwt`].
+const JWT_HEADER: &str = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9";
+
+/// HTTP status servers commonly use to signal that a request is missing a
+/// required session/CSRF token.
+const SESSION_CHALLENGE_STATUS: u16 = 409;
+
+#[derive(Serialize)]
+struct JwtClaims {
+    iat: u64,
+}
+
+/// A failed attempt at [`HttpTransport::send_raw`], carrying everything the
+/// retry policy needs to decide what to do next.
+struct HttpFailure {
+    error: Error,
+    status: Option<u16>,
+    retry_after: Option<Duration>,
+}
+
+impl HttpFailure {
+    /// A failure that never got a response at all (e.g. the request failed
+    /// to build or the connection couldn't be made).
+    fn without_response(error: Error) -> Self {
+        Self {
+            error,
+            status: None,
+            retry_after: None,
+        }
+    }
+}
+
+/// TLS settings accumulated by the `with_*` builder methods and reapplied
+/// to the underlying `reqwest::Client` every time one of them is called.
+#[derive(Clone)]
+struct TlsConfig {
+    native_roots: bool,
+    extra_root_certificates: Vec<Vec<u8>>,
+    client_identity: Option<(Vec<u8>, String)>,
+    danger_accept_invalid_certs: bool,
+}
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        Self {
+            // Mirrors `reqwest::ClientBuilder`'s own default of trusting the
+            // platform's built-in root store; `false` here would make every
+            // plain HTTPS endpoint fail certificate validation out of the
+            // box.
+            native_roots: true,
+            extra_root_certificates: Vec::new(),
+            client_identity: None,
+            danger_accept_invalid_certs: false,
+        }
+    }
+}
+
+/// Connection-level settings accumulated by the `with_*` builder methods
+/// and reapplied to the underlying `reqwest::Client` every time one of them
+/// is called.
+#[derive(Clone)]
+struct ClientConfig {
+    connect_timeout: Duration,
+    request_timeout: Duration,
+    pool_idle_timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+    default_headers: reqwest::header::HeaderMap,
+    proxy: Option<String>,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(10),
+            request_timeout: Duration::from_secs(30),
+            pool_idle_timeout: None,
+            pool_max_idle_per_host: None,
+            default_headers: reqwest::header::HeaderMap::new(),
+            proxy: None,
+        }
+    }
+}
+
 /// HTTP transport
 #[derive(Clone)]
 pub struct HttpTransport {
@@ -14,16 +104,55 @@ pub struct HttpTransport {
     bearer_auth_token: Option<String>,
     basic_auth_username: Option<String>,
     basic_auth_password: Option<String>,
+    jwt_secret: Option<[u8; 32]>,
+    retry: Option<RetryPolicy>,
+    tls: TlsConfig,
+    config: ClientConfig,
+    session_header: Option<reqwest::header::HeaderName>,
+    session_token: Arc<Mutex<Option<String>>>,
     client: reqwest::Client,
 }
 
 impl HttpTransport {
     fn new_client() -> reqwest::Client {
-        reqwest::Client::builder()
-            .connect_timeout(Duration::from_secs(10))
-            .timeout(Duration::from_secs(30))
-            .build()
-            .expect("ClientBuilder config is valid; qed")
+        Self::build_client(&TlsConfig::default(), &ClientConfig::default())
+            .expect("default TLS and client config is valid; qed")
+    }
+
+    /// Build a `reqwest::Client` from the given TLS and connection settings.
+    fn build_client(tls: &TlsConfig, config: &ClientConfig) -> Result<reqwest::Client> {
+        let mut builder = reqwest::Client::builder()
+            .connect_timeout(config.connect_timeout)
+            .timeout(config.request_timeout)
+            .default_headers(config.default_headers.clone())
+            .tls_built_in_root_certs(tls.native_roots)
+            .danger_accept_invalid_certs(tls.danger_accept_invalid_certs);
+
+        if let Some(pool_idle_timeout) = config.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(pool_idle_timeout);
+        }
+        if let Some(pool_max_idle_per_host) = config.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(pool_max_idle_per_host);
+        }
+        if let Some(proxy) = &config.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+        }
+
+        for pem in &tls.extra_root_certificates {
+            builder = builder.add_root_certificate(reqwest::Certificate::from_pem(pem)?);
+        }
+
+        if let Some((identity_der, password)) = &tls.client_identity {
+            builder = builder.identity(reqwest::Identity::from_pkcs12_der(identity_der, password)?);
+        }
+
+        Ok(builder.build()?)
+    }
+
+    /// Rebuild `self.client` from the current `self.tls` and `self.config`.
+    fn rebuild_client(&mut self) -> Result<()> {
+        self.client = Self::build_client(&self.tls, &self.config)?;
+        Ok(())
     }
 
     /// Create a new HTTP transport with given `url`.
@@ -34,6 +163,12 @@ impl HttpTransport {
             bearer_auth_token: None,
             basic_auth_username: None,
             basic_auth_password: None,
+            jwt_secret: None,
+            retry: None,
+            tls: TlsConfig::default(),
+            config: ClientConfig::default(),
+            session_header: None,
+            session_token: Arc::new(Mutex::new(None)),
             client: Self::new_client(),
         }
     }
@@ -46,6 +181,12 @@ impl HttpTransport {
             bearer_auth_token: Some(token.into()),
             basic_auth_username: None,
             basic_auth_password: None,
+            jwt_secret: None,
+            retry: None,
+            tls: TlsConfig::default(),
+            config: ClientConfig::default(),
+            session_header: None,
+            session_token: Arc::new(Mutex::new(None)),
             client: Self::new_client(),
         }
     }
@@ -62,13 +203,182 @@ impl HttpTransport {
             bearer_auth_token: None,
             basic_auth_username: Some(username.into()),
             basic_auth_password: Some(password.into()),
+            jwt_secret: None,
+            retry: None,
+            tls: TlsConfig::default(),
+            config: ClientConfig::default(),
+            session_header: None,
+            session_token: Arc::new(Mutex::new(None)),
             client: Self::new_client(),
         }
     }
 
-    async fn send_request(&self, request: &Request) -> Result<Response> {
-        let builder = self.client.post(&self.url).json(request);
-        let builder = if let Some(token) = &self.bearer_auth_token {
+    /// Create a new HTTP transport with given `url`, authenticating every
+    /// request with a freshly minted HS256 JWT signed with `secret`.
+    ///
+    /// This is the scheme used by Ethereum Engine API endpoints and other
+    /// JWT-gated JSON-RPC services: the token carries only an `iat` claim
+    /// and must be regenerated for every call, since servers reject tokens
+    /// whose `iat` has drifted too far from their own clock.
+    pub fn new_with_jwt<U: Into<String>>(url: U, secret: [u8; 32]) -> Self {
+        Self {
+            id: Default::default(),
+            url: url.into(),
+            bearer_auth_token: None,
+            basic_auth_username: None,
+            basic_auth_password: None,
+            jwt_secret: Some(secret),
+            retry: None,
+            tls: TlsConfig::default(),
+            config: ClientConfig::default(),
+            session_header: None,
+            session_token: Arc::new(Mutex::new(None)),
+            client: Self::new_client(),
+        }
+    }
+
+    /// Retry failed calls according to `policy` instead of surfacing the
+    /// first error. JSON-RPC requests carry their own id, so re-sending the
+    /// identical [`Request`] on retry is safe.
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Set the TCP connect timeout. Defaults to 10 seconds.
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.config.connect_timeout = timeout;
+        self.rebuild_client()
+            .expect("changing a timeout does not invalidate the client; qed");
+        self
+    }
+
+    /// Set the timeout for an entire request, including the response body.
+    /// Defaults to 30 seconds.
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.config.request_timeout = timeout;
+        self.rebuild_client()
+            .expect("changing a timeout does not invalidate the client; qed");
+        self
+    }
+
+    /// Set how long an idle pooled connection is kept before being closed.
+    pub fn with_pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.config.pool_idle_timeout = Some(timeout);
+        self.rebuild_client()
+            .expect("changing a timeout does not invalidate the client; qed");
+        self
+    }
+
+    /// Set the maximum number of idle connections kept per host.
+    pub fn with_pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.config.pool_max_idle_per_host = Some(max);
+        self.rebuild_client()
+            .expect("changing the pool size does not invalidate the client; qed");
+        self
+    }
+
+    /// Always send `name: value` with every request, e.g. a custom
+    /// `User-Agent` or an API-key header.
+    pub fn with_default_header(
+        mut self,
+        name: reqwest::header::HeaderName,
+        value: reqwest::header::HeaderValue,
+    ) -> Result<Self> {
+        self.config.default_headers.insert(name, value);
+        self.rebuild_client()?;
+        Ok(self)
+    }
+
+    /// Route requests through the given HTTP proxy.
+    pub fn with_proxy(mut self, proxy_url: impl Into<String>) -> Result<Self> {
+        self.config.proxy = Some(proxy_url.into());
+        self.rebuild_client()?;
+        Ok(self)
+    }
+
+    /// Support servers that hand out a session/CSRF token and reject
+    /// requests lacking it with [`SESSION_CHALLENGE_STATUS`], then accept a
+    /// retry carrying that token in `header_name`.
+    ///
+    /// Once a challenge response is seen, the token it returns in
+    /// `header_name` is cached (shared across clones) and attached to every
+    /// later request, until another challenge replaces it.
+    pub fn with_session_header(mut self, header_name: impl AsRef<str>) -> Result<Self> {
+        self.session_header = Some(reqwest::header::HeaderName::from_bytes(
+            header_name.as_ref().as_bytes(),
+        )?);
+        Ok(self)
+    }
+
+    /// Trust the OS's built-in root certificate store. This is already the
+    /// default, so on a fresh transport this only rebuilds the client. It
+    /// does not undo
+    /// [`danger_accept_invalid_certs`](Self::danger_accept_invalid_certs),
+    /// which is a separate setting.
+    pub fn with_native_roots(mut self) -> Self {
+        self.tls.native_roots = true;
+        self.rebuild_client()
+            .expect("enabling native roots does not invalidate the client; qed");
+        self
+    }
+
+    /// Trust an extra PEM-encoded CA certificate, for endpoints that sit
+    /// behind a private certificate authority.
+    pub fn with_root_certificate(mut self, pem: impl Into<Vec<u8>>) -> Result<Self> {
+        self.tls.extra_root_certificates.push(pem.into());
+        self.rebuild_client()?;
+        Ok(self)
+    }
+
+    /// Present a PKCS#12-encoded client identity (certificate + private
+    /// key), protected by `password`, for mutual-TLS JSON-RPC endpoints.
+    ///
+    /// PKCS#12/DER is used rather than PEM because `reqwest::Identity`'s
+    /// PEM constructor is only available under its rustls backend, which
+    /// this crate doesn't select; the PKCS#12 constructor works under the
+    /// default backend instead.
+    pub fn with_client_identity(
+        mut self,
+        der: impl Into<Vec<u8>>,
+        password: impl Into<String>,
+    ) -> Result<Self> {
+        self.tls.client_identity = Some((der.into(), password.into()));
+        self.rebuild_client()?;
+        Ok(self)
+    }
+
+    /// Accept invalid (e.g. self-signed) server certificates. Only meant
+    /// for talking to dev nodes; never enable this in production.
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.tls.danger_accept_invalid_certs = accept;
+        self.rebuild_client()
+            .expect("toggling cert verification does not invalidate the client; qed");
+        self
+    }
+
+    /// Mint a fresh HS256 JWT over `secret`, claiming the current UNIX
+    /// timestamp as `iat`.
+    fn mint_jwt(secret: &[u8; 32]) -> Result<String> {
+        let iat = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let claims = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&JwtClaims { iat })?);
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length; qed");
+        mac.update(format!("{JWT_HEADER}.{claims}").as_bytes());
+        let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+
+        Ok(format!("{JWT_HEADER}.{claims}.{signature}"))
+    }
+
+    /// Apply whichever auth scheme this transport was constructed with to an
+    /// outgoing request builder.
+    fn apply_auth(&self, builder: reqwest::RequestBuilder) -> Result<reqwest::RequestBuilder> {
+        let builder = if let Some(secret) = &self.jwt_secret {
+            builder.bearer_auth(Self::mint_jwt(secret)?)
+        } else if let Some(token) = &self.bearer_auth_token {
             builder.bearer_auth(token)
         } else {
             builder
@@ -80,7 +390,127 @@ impl HttpTransport {
             builder
         };
 
-        Ok(builder.send().await?.json().await?)
+        Ok(builder)
+    }
+
+    /// The currently cached session token, if a challenge response has ever
+    /// handed one out.
+    fn session_token(&self) -> Option<String> {
+        self.session_token
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Parse a `Retry-After` header as a plain integer number of seconds
+    /// (the delta-seconds form; the HTTP-date form isn't handled).
+    fn parse_retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+        headers
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok())
+            .map(Duration::from_secs)
+    }
+
+    /// Pull the fresh session token out of a challenge response's headers,
+    /// if it sent one back under `header_name`.
+    fn extract_session_token(
+        headers: &reqwest::header::HeaderMap,
+        header_name: &reqwest::header::HeaderName,
+    ) -> Option<String> {
+        headers
+            .get(header_name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_owned())
+    }
+
+    /// Send `request` once, returning the HTTP status and any `Retry-After`
+    /// header alongside a failure so [`send_request`](Self::send_request)
+    /// can hand all three to the retry policy. A response carrying
+    /// [`SESSION_CHALLENGE_STATUS`] updates the cached session token as a
+    /// side effect.
+    async fn send_raw(&self, request: &Request) -> std::result::Result<Response, HttpFailure> {
+        let mut builder = self
+            .apply_auth(self.client.post(&self.url).json(request))
+            .map_err(HttpFailure::without_response)?;
+        if let (Some(header_name), Some(token)) = (&self.session_header, self.session_token()) {
+            builder = builder.header(header_name, token);
+        }
+
+        let response = builder
+            .send()
+            .await
+            .map_err(|err| HttpFailure::without_response(Error::from(err)))?;
+        let status = response.status();
+
+        if status.as_u16() == SESSION_CHALLENGE_STATUS {
+            if let Some(header_name) = &self.session_header {
+                if let Some(token) = Self::extract_session_token(response.headers(), header_name) {
+                    *self
+                        .session_token
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(token);
+                }
+            }
+        }
+
+        if !status.is_success() {
+            let retry_after = Self::parse_retry_after(response.headers());
+            return Err(HttpFailure {
+                error: Error::Transport(format!("server returned HTTP {status}")),
+                status: Some(status.as_u16()),
+                retry_after,
+            });
+        }
+
+        response.json().await.map_err(|err| HttpFailure {
+            error: Error::from(err),
+            status: Some(status.as_u16()),
+            retry_after: None,
+        })
+    }
+
+    /// Send `request`, transparently replaying it once with a freshly
+    /// cached session token if the server rejects it with
+    /// [`SESSION_CHALLENGE_STATUS`].
+    async fn send_once(&self, request: &Request) -> std::result::Result<Response, HttpFailure> {
+        match self.send_raw(request).await {
+            Err(failure)
+                if failure.status == Some(SESSION_CHALLENGE_STATUS)
+                    && self.session_header.is_some() =>
+            {
+                self.send_raw(request).await
+            }
+            outcome => outcome,
+        }
+    }
+
+    async fn send_request(&self, request: &Request) -> Result<Response> {
+        let Some(retry) = &self.retry else {
+            return self.send_once(request).await.map_err(|failure| failure.error);
+        };
+
+        let mut attempt = 0;
+        loop {
+            match self.send_once(request).await {
+                Ok(response) => return Ok(response),
+                Err(failure) => {
+                    if attempt >= retry.max_retries {
+                        return Err(failure.error);
+                    }
+                    match retry
+                        .logic
+                        .should_retry(&failure.error, failure.status, failure.retry_after)
+                    {
+                        RetryAction::DontRetry => return Err(failure.error),
+                        RetryAction::Fatal(reason) => return Err(Error::Transport(reason)),
+                        RetryAction::Retry => tokio::time::sleep(retry.delay_for(attempt)).await,
+                        RetryAction::RetryAfter(duration) => tokio::time::sleep(duration).await,
+                    }
+                    attempt += 1;
+                }
+            }
+        }
     }
 }
 
@@ -103,4 +533,221 @@ impl Transport for HttpTransport {
 }
 
 #[async_trait::async_trait]
-impl BatchTransport for HttpTransport {}
+impl BatchTransport for HttpTransport {
+    async fn send_batch(
+        &self,
+        calls: impl IntoIterator<Item = (RequestId, Call)> + Send,
+    ) -> Result<Vec<(RequestId, Result<Response>)>> {
+        let calls: Vec<(RequestId, Call)> = calls.into_iter().collect();
+        if calls.is_empty() {
+            return Err(Error::Transport("cannot send an empty batch".into()));
+        }
+
+        let body: Vec<&Call> = calls.iter().map(|(_, call)| call).collect();
+        let mut builder = self.apply_auth(self.client.post(&self.url).json(&body))?;
+        if let (Some(header_name), Some(token)) = (&self.session_header, self.session_token()) {
+            builder = builder.header(header_name, token);
+        }
+        let raw: serde_json::Value = builder.send().await?.json().await?;
+
+        route_batch_responses(raw, calls)
+    }
+}
+
+/// Match each call in a server's raw JSON batch reply back to the call it
+/// answers, by JSON-RPC `id`.
+///
+/// Split out of [`HttpTransport::send_batch`] so the id-routing and
+/// notification/error-object edge cases can be unit tested without a live
+/// server.
+fn route_batch_responses(
+    raw: serde_json::Value,
+    calls: Vec<(RequestId, Call)>,
+) -> Result<Vec<(RequestId, Result<Response>)>> {
+    let values = match raw {
+        serde_json::Value::Array(values) => values,
+        // A server rejecting the whole batch may reply with a single
+        // error object instead of a JSON array; surface it as the
+        // error for every call we sent rather than losing it trying to
+        // match it against an `id` it doesn't carry.
+        single => {
+            let reason = format!("server rejected the batch: {single}");
+            return Ok(calls
+                .into_iter()
+                .filter_map(|(id, call)| {
+                    if call.is_notification() {
+                        None
+                    } else {
+                        Some((id, Err(Error::Transport(reason.clone()))))
+                    }
+                })
+                .collect());
+        }
+    };
+
+    let responses: Vec<Response> = values
+        .into_iter()
+        .map(|value| Ok(serde_json::from_value(value)?))
+        .collect::<Result<_>>()?;
+
+    // Servers may reorder a batch, so route each response back to its
+    // originating call by matching on the JSON-RPC `id` rather than
+    // position.
+    let mut by_id: std::collections::HashMap<RequestId, Response> = responses
+        .into_iter()
+        .filter_map(|response| response.id().map(|id| (id, response)))
+        .collect();
+
+    Ok(calls
+        .into_iter()
+        .filter_map(|(id, call)| {
+            match by_id.remove(&id) {
+                Some(response) => Some((id, Ok(response))),
+                // Notifications carry no id, so the server sends no
+                // response element for them; that is not an error, so
+                // there's simply no result to report for it.
+                None if call.is_notification() => None,
+                None => Some((
+                    id,
+                    Err(Error::Transport(format!(
+                        "batch response missing for request {id}"
+                    ))),
+                )),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn method_call(id: RequestId) -> (RequestId, Call) {
+        (
+            id,
+            Call::MethodCall(MethodCall {
+                jsonrpc: Some(Version::V2),
+                id,
+                method: "ping".into(),
+                params: Params::None,
+            }),
+        )
+    }
+
+    fn notification(id: RequestId) -> (RequestId, Call) {
+        (
+            id,
+            Call::Notification(crate::types::Notification {
+                jsonrpc: Some(Version::V2),
+                method: "ping".into(),
+                params: Params::None,
+            }),
+        )
+    }
+
+    #[test]
+    fn route_batch_responses_matches_by_id_even_when_reordered() {
+        let raw = serde_json::json!([
+            {"jsonrpc": "2.0", "result": 2, "id": 2},
+            {"jsonrpc": "2.0", "result": 1, "id": 1},
+        ]);
+        let calls = vec![method_call(1), method_call(2)];
+
+        let results = route_batch_responses(raw, calls).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].1.is_ok());
+        assert!(results[1].1.is_ok());
+        assert_eq!(results[0].0, 1);
+        assert_eq!(results[0].1.as_ref().unwrap().id(), Some(1));
+        assert_eq!(results[1].0, 2);
+        assert_eq!(results[1].1.as_ref().unwrap().id(), Some(2));
+    }
+
+    #[test]
+    fn route_batch_responses_omits_notifications_without_a_response() {
+        let raw = serde_json::json!([{"jsonrpc": "2.0", "result": 1, "id": 1}]);
+        let calls = vec![method_call(1), notification(2)];
+
+        let results = route_batch_responses(raw, calls).unwrap();
+
+        // The notification gets no response element back, and that is not
+        // an error: it simply has no entry in the routed results.
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, 1);
+        assert!(results[0].1.is_ok());
+    }
+
+    #[test]
+    fn route_batch_responses_errors_a_missing_non_notification_response() {
+        let raw = serde_json::json!([]);
+        let calls = vec![method_call(1)];
+
+        let results = route_batch_responses(raw, calls).unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].1.is_err());
+    }
+
+    #[test]
+    fn route_batch_responses_surfaces_a_single_error_object_for_every_call() {
+        let raw = serde_json::json!({"jsonrpc": "2.0", "error": {"code": -32600, "message": "invalid batch"}, "id": null});
+        let calls = vec![method_call(1), method_call(2), notification(3)];
+
+        let results = route_batch_responses(raw, calls).unwrap();
+
+        // The notification still gets no entry; both real calls see the
+        // rejection instead of the generic "missing response" error.
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|(_, result)| result.is_err()));
+    }
+
+    #[test]
+    fn parse_retry_after_reads_delta_seconds() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::RETRY_AFTER, "30".parse().unwrap());
+
+        assert_eq!(
+            HttpTransport::parse_retry_after(&headers),
+            Some(Duration::from_secs(30))
+        );
+    }
+
+    #[test]
+    fn extract_session_token_reads_the_challenge_header() {
+        let header_name = reqwest::header::HeaderName::from_static("x-session-token");
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(header_name.clone(), "fresh-token".parse().unwrap());
+
+        assert_eq!(
+            HttpTransport::extract_session_token(&headers, &header_name),
+            Some("fresh-token".to_owned())
+        );
+    }
+
+    #[test]
+    fn extract_session_token_is_none_when_the_header_is_missing() {
+        let header_name = reqwest::header::HeaderName::from_static("x-session-token");
+        let headers = reqwest::header::HeaderMap::new();
+
+        assert_eq!(
+            HttpTransport::extract_session_token(&headers, &header_name),
+            None
+        );
+    }
+
+    #[test]
+    fn parse_retry_after_is_none_when_absent_or_not_delta_seconds() {
+        assert_eq!(
+            HttpTransport::parse_retry_after(&reqwest::header::HeaderMap::new()),
+            None
+        );
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            reqwest::header::RETRY_AFTER,
+            "Wed, 21 Oct 2026 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(HttpTransport::parse_retry_after(&headers), None);
+    }
+}
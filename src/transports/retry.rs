@@ -0,0 +1,118 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::errors::Error;
+
+/// What an [`HttpTransport`](crate::transports::http::HttpTransport) should
+/// do after a call fails.
+#[derive(Debug, Clone)]
+pub enum RetryAction {
+    /// Retry immediately (well, after the policy's backoff delay).
+    Retry,
+    /// Retry, but wait the given duration first (e.g. honoring a server's
+    /// `Retry-After` header) instead of the policy's own backoff delay.
+    RetryAfter(Duration),
+    /// Give up and surface the error to the caller.
+    DontRetry,
+    /// Give up permanently with an explanatory reason, regardless of how
+    /// many attempts remain.
+    Fatal(String),
+}
+
+/// Decides whether a failed call should be retried.
+///
+/// `status` is the HTTP status code of the response when one was received
+/// but treated as a failure (e.g. a 5xx); it is `None` when the request
+/// never got a response at all (connection refused, timed out, ...).
+/// `retry_after` is the server's `Retry-After` header, parsed into a
+/// [`Duration`] when one was present on that response.
+pub trait RetryLogic: Send + Sync {
+    fn should_retry(
+        &self,
+        err: &Error,
+        status: Option<u16>,
+        retry_after: Option<Duration>,
+    ) -> RetryAction;
+}
+
+/// The retry policy used by [`RetryPolicy::default`]: retries connection
+/// and timeout failures, plus the HTTP statuses a well-behaved server uses
+/// to signal a transient problem (429, 500, 502, 503, 504), honoring a
+/// `Retry-After` header when the server sent one.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultRetryLogic;
+
+impl RetryLogic for DefaultRetryLogic {
+    fn should_retry(
+        &self,
+        _err: &Error,
+        status: Option<u16>,
+        retry_after: Option<Duration>,
+    ) -> RetryAction {
+        match status {
+            Some(429 | 500 | 502 | 503 | 504) => match retry_after {
+                Some(retry_after) => RetryAction::RetryAfter(retry_after),
+                None => RetryAction::Retry,
+            },
+            Some(_) => RetryAction::DontRetry,
+            // No response at all means the request never reached the
+            // server (or its reply never reached us); treat it the same
+            // as a transient server error.
+            None => RetryAction::Retry,
+        }
+    }
+}
+
+/// Configures how [`HttpTransport::with_retry`](crate::transports::http::HttpTransport::with_retry)
+/// retries failed calls: which [`RetryLogic`] decides whether to retry, how
+/// many attempts to make, and the exponential backoff delay between them.
+#[derive(Clone)]
+pub struct RetryPolicy {
+    pub(crate) logic: Arc<dyn RetryLogic>,
+    pub(crate) max_retries: u32,
+    pub(crate) base_delay: Duration,
+    pub(crate) multiplier: f64,
+}
+
+impl RetryPolicy {
+    /// Build a policy around the given [`RetryLogic`], with a default of 3
+    /// retries, a 200ms base delay and a 2x backoff multiplier.
+    pub fn new(logic: impl RetryLogic + 'static) -> Self {
+        Self {
+            logic: Arc::new(logic),
+            max_retries: 3,
+            base_delay: Duration::from_millis(200),
+            multiplier: 2.0,
+        }
+    }
+
+    /// Set the maximum number of retries (not counting the initial attempt).
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the delay before the first retry; later retries scale this by
+    /// `multiplier` per attempt.
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Set the exponential backoff multiplier applied per retry attempt.
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// The backoff delay before retry number `attempt` (0-indexed).
+    pub(crate) fn delay_for(&self, attempt: u32) -> Duration {
+        self.base_delay.mul_f64(self.multiplier.powi(attempt as i32))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(DefaultRetryLogic)
+    }
+}